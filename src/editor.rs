@@ -0,0 +1,146 @@
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::process::Command;
+
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+
+/// Opens `$EDITOR` (falling back to `vi`, then `nano`) on a temp file seeded
+/// with `initial`, and returns the buffer's contents once the editor exits.
+///
+/// # Arguments
+///
+/// * `initial` - The content to seed the temp file with.
+///
+/// # Returns
+///
+/// * `Result<String, Box<dyn Error>>` - The edited buffer, or an error if the editor could not be launched or read back.
+fn edit_content(initial: &str) -> Result<String, Box<dyn Error>> {
+    let mut path = env::temp_dir();
+    let suffix: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(8)
+        .map(char::from)
+        .collect();
+    path.push(format!("todo-{}.tmp", suffix));
+
+    fs::write(&path, initial)?;
+
+    let editors: Vec<Vec<String>> = match env::var("EDITOR") {
+        Ok(editor) => vec![editor.split_whitespace().map(String::from).collect()],
+        Err(_) => vec![vec!["vi".to_string()], vec!["nano".to_string()]],
+    };
+
+    let mut launched = false;
+    let mut last_error = None;
+
+    for editor in &editors {
+        let (program, args) = match editor.split_first() {
+            Some(parts) => parts,
+            None => continue,
+        };
+
+        match Command::new(program).args(args).arg(&path).status() {
+            Ok(status) if status.success() => {
+                launched = true;
+                break;
+            }
+            Ok(status) => {
+                fs::remove_file(&path).ok();
+                return Err(format!("Editor '{}' exited with {}.", editor.join(" "), status).into());
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    if !launched {
+        fs::remove_file(&path).ok();
+        let tried = editors
+            .iter()
+            .map(|editor| editor.join(" "))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(match last_error {
+            Some(e) => format!("Failed to launch an editor (tried: {}): {}", tried, e).into(),
+            None => format!("Failed to launch an editor (tried: {}).", tried).into(),
+        });
+    }
+
+    let content = fs::read_to_string(&path)?;
+    fs::remove_file(&path).ok();
+
+    Ok(content)
+}
+
+/// Splits an edited buffer back into a title and description using `# Title`
+/// and `# Description` section headers.
+///
+/// # Arguments
+///
+/// * `content` - The buffer read back from the editor.
+fn parse_sections(content: &str) -> (Option<String>, Option<String>) {
+    let mut title = None;
+    let mut description = String::new();
+    let mut section: Option<&str> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.eq_ignore_ascii_case("# title") {
+            section = Some("title");
+            continue;
+        }
+        if trimmed.eq_ignore_ascii_case("# description") {
+            section = Some("description");
+            continue;
+        }
+
+        match section {
+            Some("title") if title.is_none() && !trimmed.is_empty() => {
+                title = Some(trimmed.to_string());
+            }
+            Some("title") => {}
+            Some("description") => {
+                description.push_str(line);
+                description.push('\n');
+            }
+            _ => {}
+        }
+    }
+
+    let description = description.trim().to_string();
+    let description = if description.is_empty() {
+        None
+    } else {
+        Some(description)
+    };
+
+    (title, description)
+}
+
+/// Composes a task's title and description via `$EDITOR`, seeding the
+/// buffer with any fields already known.
+///
+/// # Arguments
+///
+/// * `title` - The current title, if any, to pre-fill.
+/// * `description` - The current description, if any, to pre-fill.
+///
+/// # Returns
+///
+/// * `Result<(String, String), Box<dyn Error>>` - The composed title and description.
+pub fn compose(title: Option<&str>, description: Option<&str>) -> Result<(String, String), Box<dyn Error>> {
+    let template = format!(
+        "# Title\n{}\n\n# Description\n{}\n",
+        title.unwrap_or(""),
+        description.unwrap_or(""),
+    );
+
+    let content = edit_content(&template)?;
+    let (parsed_title, parsed_description) = parse_sections(&content);
+
+    let title = parsed_title.ok_or("No title was provided in the editor buffer.")?;
+    let description = parsed_description.unwrap_or_default();
+
+    Ok((title, description))
+}
@@ -0,0 +1,93 @@
+use std::error::Error;
+use std::path::Path;
+use std::process::Command;
+
+/// Runs the `git` binary in `dir` and returns stdout on success.
+///
+/// # Arguments
+///
+/// * `dir` - The directory to run the command in.
+/// * `args` - The arguments to pass to `git`.
+///
+/// # Returns
+///
+/// * `Result<String, Box<dyn Error>>` - The command's stdout, or an error describing the failure.
+fn run_git(dir: &str, args: &[&str]) -> Result<String, Box<dyn Error>> {
+    let output = Command::new("git").current_dir(dir).args(args).output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let detail = if stderr.trim().is_empty() { stdout.trim() } else { stderr.trim() };
+        return Err(format!("git {}: {}", args.join(" "), detail).into());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Initializes a git repository in `dir` if one does not already exist.
+///
+/// # Arguments
+///
+/// * `dir` - The directory that should become a git repository.
+///
+/// # Returns
+///
+/// * `Result<(), Box<dyn Error>>` - A result indicating whether the repository is ready.
+fn ensure_repo(dir: &str) -> Result<(), Box<dyn Error>> {
+    if !Path::new(dir).join(".git").exists() {
+        run_git(dir, &["init"])?;
+    }
+
+    Ok(())
+}
+
+/// Commits the given file to the git repository containing it, creating the
+/// repository first if it does not exist.
+///
+/// # Arguments
+///
+/// * `dir` - The directory containing the file, treated as the repository root.
+/// * `file_name` - The name of the file to stage, relative to `dir`.
+/// * `message` - The commit message.
+///
+/// # Returns
+///
+/// * `Result<(), Box<dyn Error>>` - A result indicating whether the commit succeeded.
+pub fn auto_commit(dir: &str, file_name: &str, message: &str) -> Result<(), Box<dyn Error>> {
+    ensure_repo(dir)?;
+    run_git(dir, &["add", file_name])?;
+
+    match run_git(dir, &["commit", "-m", message]) {
+        Ok(_) => Ok(()),
+        // Nothing to commit is not a failure: the file may be unchanged.
+        Err(e)
+            if e.to_string().contains("nothing to commit")
+                || e.to_string().contains("nothing added to commit") =>
+        {
+            Ok(())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Commits any pending changes, pulls with rebase, and pushes to `remote`.
+///
+/// # Arguments
+///
+/// * `dir` - The directory containing the repository.
+/// * `file_name` - The name of the file to stage before committing.
+/// * `remote` - The name of the remote to pull from and push to.
+///
+/// # Returns
+///
+/// * `Result<String, Box<dyn Error>>` - A result indicating whether the sync succeeded.
+pub fn sync(dir: &str, file_name: &str, remote: &str) -> Result<String, Box<dyn Error>> {
+    ensure_repo(dir)?;
+
+    auto_commit(dir, file_name, "sync todo list")?;
+    run_git(dir, &["pull", "--rebase", remote])?;
+    run_git(dir, &["push", remote])?;
+
+    Ok(format!("Synced with remote '{}'.", remote))
+}
@@ -1,14 +1,22 @@
+mod editor;
+mod git;
 mod task;
 use clap::{Args, Parser, Subcommand};
 use std::env;
-use task::Tasks;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use task::{Priority, SortBy, Tasks};
 
 /// The main CLI struct.
 #[derive(Parser, Debug)]
 #[command(author, version, about = "Simple todo CLI")]
 struct Cli {
     #[command(subcommand)]
-    command: Commands,
+    command: Option<Commands>,
+
+    /// Auto-commit the todo file to git after a mutating command.
+    #[arg(long, global = true)]
+    auto_commit: bool,
 }
 
 /// The available commands for the CLI.
@@ -18,6 +26,12 @@ enum Commands {
     Remove(RemoveArgs),
     Edit(EditArgs),
     List(ListArgs),
+    Sync(SyncArgs),
+    Log(LogArgs),
+    Summary(SummaryArgs),
+    Depend(DependArgs),
+    #[command(about = "Start an interactive REPL")]
+    Repl,
 }
 
 /// The arguments for the "add" command.
@@ -25,10 +39,26 @@ enum Commands {
 #[command(about = "Add todo task")]
 struct AddArgs {
     #[arg(short, long)]
-    title: String,
+    title: Option<String>,
 
     #[arg(short, long)]
-    description: String,
+    description: Option<String>,
+
+    /// Due date in RFC3339 format, e.g. 2025-01-21T00:00:00Z.
+    #[arg(long)]
+    due: Option<String>,
+
+    /// Task priority (low, medium, high). Defaults to low.
+    #[arg(long, value_enum)]
+    priority: Option<Priority>,
+
+    /// Comma-separated list of tags, e.g. work,urgent.
+    #[arg(long)]
+    tags: Option<String>,
+
+    /// Compose the title and description in `$EDITOR`.
+    #[arg(long)]
+    editor: bool,
 }
 
 /// The arguments for the "edit" command.
@@ -46,6 +76,22 @@ struct EditArgs {
 
     #[arg(short, long)]
     is_done: Option<bool>,
+
+    /// Due date in RFC3339 format, e.g. 2025-01-21T00:00:00Z.
+    #[arg(long)]
+    due: Option<String>,
+
+    /// Task priority (low, medium, high).
+    #[arg(long, value_enum)]
+    priority: Option<Priority>,
+
+    /// Comma-separated list of tags, e.g. work,urgent.
+    #[arg(long)]
+    tags: Option<String>,
+
+    /// Compose the title and description in `$EDITOR`, pre-filled with the current values.
+    #[arg(long)]
+    editor: bool,
 }
 
 /// The arguments for the "remove" command.
@@ -59,46 +105,354 @@ struct RemoveArgs {
 /// The arguments for the "list" command.
 #[derive(Args, Debug)]
 #[command(about = "Show todo tasks")]
-struct ListArgs;
+struct ListArgs {
+    /// Only show tasks that have this tag.
+    #[arg(long)]
+    tag: Option<String>,
 
-fn main() {
-    let args = Cli::parse();
+    /// Only show tasks whose due date has passed.
+    #[arg(long)]
+    overdue: bool,
 
-    let todo_file = env::var("TODO_FILE").unwrap_or_else(|_| "/tmp/todo/todo.csv".to_string());
+    /// Sort tasks by due date or priority.
+    #[arg(long, value_enum)]
+    sort: Option<SortBy>,
+}
+
+/// The arguments for the "sync" command.
+#[derive(Args, Debug)]
+#[command(about = "Sync the todo file with a git remote")]
+struct SyncArgs {
+    /// The git remote to pull from and push to.
+    #[arg(long, default_value = "origin")]
+    remote: String,
+}
 
-    let mut tasks = Tasks::new(todo_file);
+/// The arguments for the "log" command.
+#[derive(Args, Debug)]
+#[command(about = "Log time spent on a task")]
+struct LogArgs {
+    #[arg()]
+    id: String,
+
+    #[arg(long)]
+    hours: Option<u32>,
+
+    #[arg(long)]
+    minutes: Option<u32>,
+}
+
+/// The arguments for the "summary" command.
+#[derive(Args, Debug)]
+#[command(about = "Show a summary of logged time grouped by day")]
+struct SummaryArgs;
+
+/// The arguments for the "depend" command.
+#[derive(Args, Debug)]
+#[command(about = "Add or remove a dependency between tasks")]
+struct DependArgs {
+    #[arg()]
+    id: String,
+
+    /// The task that `id` depends on.
+    #[arg(long)]
+    on: Option<String>,
+
+    /// Remove the dependency instead of adding it.
+    #[arg(long)]
+    remove: bool,
+}
+
+/// Splits the todo file path into the directory that should act as the git
+/// repository root and the file name to stage.
+///
+/// # Arguments
+///
+/// * `todo_file` - The path to the CSV file that stores the tasks.
+fn repo_location(todo_file: &str) -> (String, String) {
+    let path = Path::new(todo_file);
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."))
+        .to_string_lossy()
+        .to_string();
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+
+    (dir, file_name)
+}
+
+/// Auto-commits the todo file if `--auto-commit` was passed, printing a
+/// warning on failure instead of aborting the command that triggered it.
+///
+/// # Arguments
+///
+/// * `todo_file` - The path to the CSV file that stores the tasks.
+/// * `enabled` - Whether `--auto-commit` was passed.
+/// * `message` - The commit message.
+fn auto_commit_if_enabled(todo_file: &str, enabled: bool, message: &str) {
+    if !enabled {
+        return;
+    }
+
+    let (dir, file_name) = repo_location(todo_file);
+    if let Err(e) = git::auto_commit(&dir, &file_name, message) {
+        eprintln!("Warning: failed to auto-commit: {}", e);
+    }
+}
+
+/// Runs a single parsed command against `tasks`.
+///
+/// # Arguments
+///
+/// * `tasks` - The task collection to operate on.
+/// * `command` - The command to run.
+/// * `todo_file` - The path to the CSV file that stores the tasks.
+/// * `auto_commit` - Whether `--auto-commit` was passed.
+fn run_command(tasks: &mut Tasks, command: Commands, todo_file: &str, auto_commit: bool) {
+    match command {
+        Commands::Add(mut add_args) => {
+            if add_args.editor || add_args.title.is_none() || add_args.description.is_none() {
+                match editor::compose(add_args.title.as_deref(), add_args.description.as_deref()) {
+                    Ok((title, description)) => {
+                        add_args.title = Some(title);
+                        add_args.description = Some(description);
+                    }
+                    Err(e) => {
+                        eprintln!("Error composing task with editor: {}", e);
+                        return;
+                    }
+                }
+            }
 
-    match args.command {
-        Commands::Add(add_args) => {
             let result = tasks.add_task(add_args);
             match result {
-                Ok(res) => println!("{}", res),
+                Ok(id) => {
+                    println!("The task was successfully added (id: {}).", id);
+                    auto_commit_if_enabled(todo_file, auto_commit, &format!("add task {}", id));
+                }
                 Err(e) => eprintln!("Error adding task:  {}", e),
             }
         }
 
         Commands::Remove(remove_args) => {
+            let message = format!("remove task {}", remove_args.id);
             let result = tasks.remove_task(remove_args);
             match result {
-                Ok(res) => println!("{}", res),
+                Ok(res) => {
+                    println!("{}", res);
+                    auto_commit_if_enabled(todo_file, auto_commit, &message);
+                }
                 Err(e) => eprintln!("Error removing task: {}", e),
             }
         }
 
-        Commands::Edit(edit_args) => {
+        Commands::Edit(mut edit_args) => {
+            if edit_args.editor {
+                let current = tasks.task_fields(&edit_args.id);
+                let (current_title, current_description) = match current {
+                    Ok(fields) => fields,
+                    Err(e) => {
+                        eprintln!("Error editing task: {}", e);
+                        return;
+                    }
+                };
+
+                match editor::compose(Some(&current_title), Some(&current_description)) {
+                    Ok((title, description)) => {
+                        edit_args.title = Some(title);
+                        edit_args.description = Some(description);
+                    }
+                    Err(e) => {
+                        eprintln!("Error composing task with editor: {}", e);
+                        return;
+                    }
+                }
+            }
+
+            let message = format!("edit task {}", edit_args.id);
             let result = tasks.edit_task(edit_args);
             match result {
-                Ok(res) => println!("{}", res),
+                Ok(res) => {
+                    println!("{}", res);
+                    auto_commit_if_enabled(todo_file, auto_commit, &message);
+                }
                 Err(e) => eprintln!("Error editing task: {}", e),
             }
         }
 
-        Commands::List(_) => {
-            let result = tasks.list_task();
+        Commands::List(list_args) => {
+            let result = tasks.list_task(list_args);
             match result {
                 Ok(_) => {}
                 Err(e) => eprintln!("Error listing tasks: {}", e),
             }
         }
+
+        Commands::Sync(sync_args) => {
+            let (dir, file_name) = repo_location(todo_file);
+            let result = git::sync(&dir, &file_name, &sync_args.remote);
+            match result {
+                Ok(res) => println!("{}", res),
+                Err(e) => eprintln!("Error syncing tasks: {}", e),
+            }
+        }
+
+        Commands::Log(log_args) => {
+            let result = tasks.log_time(log_args);
+            match result {
+                Ok(res) => {
+                    println!("{}", res);
+                    auto_commit_if_enabled(todo_file, auto_commit, "log time");
+                }
+                Err(e) => eprintln!("Error logging time: {}", e),
+            }
+        }
+
+        Commands::Summary(_) => {
+            let result = tasks.summary();
+            match result {
+                Ok(_) => {}
+                Err(e) => eprintln!("Error printing summary: {}", e),
+            }
+        }
+
+        Commands::Depend(depend_args) => {
+            let result = tasks.depend_task(depend_args);
+            match result {
+                Ok(res) => {
+                    println!("{}", res);
+                    auto_commit_if_enabled(todo_file, auto_commit, "update dependencies");
+                }
+                Err(e) => eprintln!("Error updating dependency: {}", e),
+            }
+        }
+
+        Commands::Repl => run_repl(tasks, todo_file, auto_commit),
+    }
+}
+
+/// Splits a line of REPL input into tokens, honoring `"…"` and `'…'` spans
+/// so multi-word arguments (e.g. a task title) survive as a single token.
+///
+/// # Arguments
+///
+/// * `line` - The raw line of input to tokenize.
+///
+/// # Returns
+///
+/// * `Result<Vec<String>, String>` - The tokens, or an error if a quote was left unclosed.
+fn tokenize(line: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut quote: Option<char> = None;
+
+    for c in line.chars() {
+        match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                } else {
+                    current.push(c);
+                }
+            }
+            None => match c {
+                '"' | '\'' => {
+                    quote = Some(c);
+                    in_token = true;
+                }
+                c if c.is_whitespace() => {
+                    if in_token {
+                        tokens.push(std::mem::take(&mut current));
+                        in_token = false;
+                    }
+                }
+                c => {
+                    current.push(c);
+                    in_token = true;
+                }
+            },
+        }
+    }
+
+    if quote.is_some() {
+        return Err("Unclosed quote in input.".to_string());
+    }
+    if in_token {
+        tokens.push(current);
+    }
+
+    Ok(tokens)
+}
+
+/// Runs an interactive REPL that reads a line at a time from stdin, parses
+/// it with the same clap `Cli` definition used for process arguments, and
+/// dispatches it through `run_command` without re-spawning the process.
+///
+/// Parse errors print clap's usage/help and keep the loop alive; `exit` or
+/// `quit` (or EOF) ends the loop.
+///
+/// # Arguments
+///
+/// * `tasks` - The task collection to operate on across iterations.
+/// * `todo_file` - The path to the CSV file that stores the tasks.
+/// * `auto_commit` - Whether `--auto-commit` was passed when entering the REPL.
+fn run_repl(tasks: &mut Tasks, todo_file: &str, auto_commit: bool) {
+    let stdin = io::stdin();
+
+    loop {
+        print!("todo> ");
+        if io::stdout().flush().is_err() {
+            break;
+        }
+
+        let mut line = String::new();
+        match stdin.lock().read_line(&mut line) {
+            Ok(0) => break, // EOF
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Error reading input: {}", e);
+                break;
+            }
+        }
+
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line == "exit" || line == "quit" {
+            break;
+        }
+
+        let mut tokens = vec!["todo".to_string()];
+        match tokenize(line) {
+            Ok(parsed) => tokens.extend(parsed),
+            Err(e) => {
+                println!("{}", e);
+                continue;
+            }
+        }
+
+        match Cli::try_parse_from(tokens) {
+            Ok(cli) => match cli.command {
+                Some(command) => run_command(tasks, command, todo_file, auto_commit || cli.auto_commit),
+                None => continue,
+            },
+            Err(e) => println!("{}", e),
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let todo_file = env::var("TODO_FILE").unwrap_or_else(|_| "/tmp/todo/todo.csv".to_string());
+
+    let mut tasks = Tasks::new(todo_file.clone());
+
+    match cli.command {
+        Some(command) => run_command(&mut tasks, command, &todo_file, cli.auto_commit),
+        None => run_repl(&mut tasks, &todo_file, cli.auto_commit),
     }
 }
@@ -2,15 +2,161 @@ use std::error::Error;
 use std::path::Path;
 use std::{fs, io};
 
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
 use prettytable::{row, Cell, Row, Table};
 use rand::distributions::Alphanumeric;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
-use crate::{AddArgs, EditArgs, RemoveArgs};
+use crate::{AddArgs, DependArgs, EditArgs, ListArgs, LogArgs, RemoveArgs};
 use csv::{Reader, Writer};
+use std::collections::{BTreeMap, HashSet};
 use std::fs::{File, OpenOptions};
 
+/// The priority of a task.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ValueEnum,
+)]
+pub enum Priority {
+    #[default]
+    Low,
+    Medium,
+    High,
+}
+
+/// The field to sort the task list by.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SortBy {
+    Due,
+    Priority,
+}
+
+/// Serializes/deserializes a task's tags as a semicolon-joined string so the
+/// CSV keeps a single column per field.
+mod tags_serde {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(tags: &[String], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&tags.join(";"))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        Ok(raw
+            .split(';')
+            .filter(|tag| !tag.is_empty())
+            .map(String::from)
+            .collect())
+    }
+}
+
+/// Serializes/deserializes a task's dependency IDs as a comma-joined string
+/// so the CSV keeps a single column per field.
+mod dependencies_serde {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(dependencies: &[String], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&dependencies.join(","))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        Ok(raw
+            .split(',')
+            .filter(|id| !id.is_empty())
+            .map(String::from)
+            .collect())
+    }
+}
+
+/// A single logged duration against a task, dated to the day it was logged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TimeEntry {
+    logged_date: String,
+    hours: u32,
+    minutes: u32,
+}
+
+impl TimeEntry {
+    /// Creates a new `TimeEntry`, normalizing `minutes >= 60` into `hours`.
+    ///
+    /// # Arguments
+    ///
+    /// * `logged_date` - The date the time was logged, in `YYYY-MM-DD` format.
+    /// * `hours` - The number of whole hours logged.
+    /// * `minutes` - The number of minutes logged, which may exceed 59.
+    fn new(logged_date: String, hours: u32, minutes: u32) -> TimeEntry {
+        TimeEntry {
+            logged_date,
+            hours: hours + minutes / 60,
+            minutes: minutes % 60,
+        }
+    }
+}
+
+/// Serializes/deserializes a task's time entries as a `|`-joined string of
+/// `date:HhMm` entries so the CSV keeps a single column per field.
+mod time_entries_serde {
+    use super::TimeEntry;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(entries: &[TimeEntry], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let packed = entries
+            .iter()
+            .map(|entry| format!("{}:{}h{}m", entry.logged_date, entry.hours, entry.minutes))
+            .collect::<Vec<_>>()
+            .join("|");
+
+        serializer.serialize_str(&packed)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<TimeEntry>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+
+        raw.split('|')
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let (logged_date, duration) = entry
+                    .split_once(':')
+                    .ok_or_else(|| serde::de::Error::custom(format!("invalid time entry: {}", entry)))?;
+                let (hours, minutes) = duration
+                    .split_once('h')
+                    .and_then(|(h, rest)| rest.strip_suffix('m').map(|m| (h, m)))
+                    .ok_or_else(|| serde::de::Error::custom(format!("invalid time entry: {}", entry)))?;
+
+                Ok(TimeEntry {
+                    logged_date: logged_date.to_string(),
+                    hours: hours
+                        .parse()
+                        .map_err(|_| serde::de::Error::custom(format!("invalid time entry: {}", entry)))?,
+                    minutes: minutes
+                        .parse()
+                        .map_err(|_| serde::de::Error::custom(format!("invalid time entry: {}", entry)))?,
+                })
+            })
+            .collect()
+    }
+}
+
 /// Represents a collection of tasks.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Tasks {
@@ -39,23 +185,54 @@ impl Tasks {
     ///
     /// # Returns
     ///
-    /// * `Result<String, Box<dyn Error>>` - A result indicating whether the task was successfully added or not.
+    /// * `Result<String, Box<dyn Error>>` - The new task's ID, or an error if the task could not be added.
     pub fn add_task(&mut self, add_args: AddArgs) -> Result<String, Box<dyn Error>> {
         self.read_tasks_from_csv()?;
 
+        let title = add_args.title.ok_or("A title is required.")?;
+        let description = add_args.description.ok_or("A description is required.")?;
+
         let new_id = self.generate_task_id();
         let is_done = false;
+        let tags = parse_tags(add_args.tags);
+        let due = parse_due(add_args.due)?;
 
         self.tasks.push(Task::new(
-            new_id,
-            add_args.title,
-            add_args.description,
+            new_id.clone(),
+            title,
+            description,
             is_done,
+            due,
+            add_args.priority.unwrap_or_default(),
+            tags,
         ));
 
         self.write_tasks_to_csv()?;
 
-        Ok("The task was successfully added.".to_string())
+        Ok(new_id)
+    }
+
+    /// Returns the current title and description for a task, used to
+    /// pre-fill the `$EDITOR` buffer for `todo edit --editor`.
+    ///
+    /// # Arguments
+    ///
+    /// * `id_or_index` - The task reference supplied on the command line.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(String, String), Box<dyn Error>>` - The task's title and description.
+    pub fn task_fields(&mut self, id_or_index: &str) -> Result<(String, String), Box<dyn Error>> {
+        self.read_tasks_from_csv()?;
+
+        let id = self.resolve_id(id_or_index)?;
+        let task = self
+            .tasks
+            .iter()
+            .find(|task| task.id == id)
+            .ok_or("Task not found.")?;
+
+        Ok((task.title.clone(), task.description.clone()))
     }
 
     /// Edits an existing task in the collection.
@@ -70,31 +247,42 @@ impl Tasks {
     pub fn edit_task(&mut self, edit_args: EditArgs) -> Result<String, Box<dyn Error>> {
         self.read_tasks_from_csv()?;
 
-        let id = edit_args.id;
-        let mut found = false;
-
-        for task in &mut self.tasks {
-            if task.id == id {
-                if let Some(title) = edit_args.title {
-                    task.title = title;
-                }
-                if let Some(description) = edit_args.description {
-                    task.description = description;
-                }
-                if let Some(is_done) = edit_args.is_done {
-                    task.is_done = is_done;
-                }
-                found = true;
-                break;
-            }
+        let id = self.resolve_id(&edit_args.id)?;
+        let index = self
+            .tasks
+            .iter()
+            .position(|task| task.id == id)
+            .ok_or("Task not found.")?;
+
+        if edit_args.is_done == Some(true) && self.is_blocked(&self.tasks[index]) {
+            return Err(
+                "Cannot mark task done: it is still blocked by incomplete dependencies.".into(),
+            );
         }
 
-        if found {
-            self.write_tasks_to_csv()?;
-            Ok("The task was successfully edited.".to_string())
-        } else {
-            Err("Task not found.".into())
+        let task = &mut self.tasks[index];
+
+        if let Some(title) = edit_args.title {
+            task.title = title;
+        }
+        if let Some(description) = edit_args.description {
+            task.description = description;
         }
+        if let Some(is_done) = edit_args.is_done {
+            task.is_done = is_done;
+        }
+        if let Some(due) = edit_args.due {
+            task.due = parse_due(Some(due))?;
+        }
+        if let Some(priority) = edit_args.priority {
+            task.priority = priority;
+        }
+        if let Some(tags) = edit_args.tags {
+            task.tags = parse_tags(Some(tags));
+        }
+
+        self.write_tasks_to_csv()?;
+        Ok("The task was successfully edited.".to_string())
     }
 
     /// Lists all the tasks in the collection.
@@ -102,21 +290,51 @@ impl Tasks {
     /// # Returns
     ///
     /// * `Result<(), Box<dyn Error>>` - A result indicating whether the tasks were successfully listed or not.
-    pub fn list_task(&mut self) -> Result<(), Box<dyn Error>> {
+    pub fn list_task(&mut self, list_args: ListArgs) -> Result<(), Box<dyn Error>> {
         self.read_tasks_from_csv()?;
 
+        let mut tasks: Vec<(usize, &Task)> = self.tasks.iter().enumerate().collect();
+
+        if let Some(tag) = &list_args.tag {
+            tasks.retain(|(_, task)| task.tags.iter().any(|t| t == tag));
+        }
+
+        if list_args.overdue {
+            tasks.retain(|(_, task)| task.is_overdue());
+        }
+
+        match list_args.sort {
+            Some(SortBy::Due) => tasks.sort_by(|a, b| a.1.due.cmp(&b.1.due)),
+            Some(SortBy::Priority) => tasks.sort_by_key(|t| std::cmp::Reverse(t.1.priority)),
+            None => {}
+        }
+
         let mut table = Table::new();
 
         // Add a header
-        table.add_row(row!["Id", "Title", "Desc", "Is Done"]);
+        table.add_row(row![
+            "#", "Id", "Title", "Desc", "Is Done", "Due", "Priority", "Tags", "Time", "Blocked"
+        ]);
 
         // Add a row and cells
-        for task in &self.tasks {
+        for (index, task) in &tasks {
+            let priority_spec = match task.priority {
+                Priority::Low => "bFg",
+                Priority::Medium => "bFy",
+                Priority::High => "bFr",
+            };
+
             table.add_row(Row::new(vec![
+                Cell::new(&(index + 1).to_string()).style_spec("ubFG"),
                 Cell::new(&task.id).style_spec("ubFG"),
                 Cell::new(&task.title).style_spec("bFG"),
                 Cell::new(&task.description).style_spec("bFG"),
                 Cell::new(if task.is_done { "Yes" } else { "No" }).style_spec("bFG"),
+                Cell::new(task.due.as_deref().unwrap_or("-")).style_spec("bFG"),
+                Cell::new(&format!("{:?}", task.priority)).style_spec(priority_spec),
+                Cell::new(&task.tags.join(", ")).style_spec("bFG"),
+                Cell::new(&format_duration(task.total_logged_minutes())).style_spec("bFG"),
+                Cell::new(if self.is_blocked(task) { "Yes" } else { "No" }).style_spec("bFG"),
             ]));
         }
 
@@ -136,8 +354,10 @@ impl Tasks {
     /// * `Result<String, Box<dyn Error>>` - A result indicating whether the task was successfully removed or not.
     pub fn remove_task(&mut self, remove_args: RemoveArgs) -> Result<String, Box<dyn Error>> {
         self.read_tasks_from_csv()?;
+
+        let id = self.resolve_id(&remove_args.id)?;
         let initial_len = self.tasks.len();
-        self.tasks.retain(|task| task.id != remove_args.id);
+        self.tasks.retain(|task| task.id != id);
 
         let new_len = self.tasks.len();
 
@@ -145,10 +365,171 @@ impl Tasks {
             return Err("Task not found.".into());
         }
 
+        for task in &mut self.tasks {
+            task.dependencies.retain(|dep| dep != &id);
+        }
+
         self.write_tasks_to_csv()?;
         Ok("The task was successfully removed.".to_string())
     }
 
+    /// Appends a time entry to a task, dated today.
+    ///
+    /// # Arguments
+    ///
+    /// * `log_args` - The arguments for logging time.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, Box<dyn Error>>` - A result indicating whether the time was successfully logged or not.
+    pub fn log_time(&mut self, log_args: LogArgs) -> Result<String, Box<dyn Error>> {
+        self.read_tasks_from_csv()?;
+
+        let id = self.resolve_id(&log_args.id)?;
+        let today = Utc::now().format("%Y-%m-%d").to_string();
+
+        let task = self
+            .tasks
+            .iter_mut()
+            .find(|task| task.id == id)
+            .ok_or("Task not found.")?;
+
+        task.time_entries.push(TimeEntry::new(
+            today,
+            log_args.hours.unwrap_or(0),
+            log_args.minutes.unwrap_or(0),
+        ));
+
+        self.write_tasks_to_csv()?;
+        Ok("The time entry was successfully logged.".to_string())
+    }
+
+    /// Prints a summary of total logged time grouped by day, across all tasks.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), Box<dyn Error>>` - A result indicating whether the summary was successfully printed or not.
+    pub fn summary(&mut self) -> Result<(), Box<dyn Error>> {
+        self.read_tasks_from_csv()?;
+
+        let mut minutes_by_day: BTreeMap<String, u32> = BTreeMap::new();
+
+        for task in &self.tasks {
+            for entry in &task.time_entries {
+                *minutes_by_day.entry(entry.logged_date.clone()).or_insert(0) +=
+                    entry.hours * 60 + entry.minutes;
+            }
+        }
+
+        let mut table = Table::new();
+        table.add_row(row!["Date", "Time"]);
+
+        for (date, total_minutes) in &minutes_by_day {
+            table.add_row(Row::new(vec![
+                Cell::new(date).style_spec("bFG"),
+                Cell::new(&format_duration(*total_minutes)).style_spec("bFG"),
+            ]));
+        }
+
+        table.printstd();
+
+        Ok(())
+    }
+
+    /// Adds or removes a dependency between two tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `depend_args` - The arguments for adding or removing a dependency.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, Box<dyn Error>>` - A result indicating whether the dependency was successfully updated or not.
+    pub fn depend_task(&mut self, depend_args: DependArgs) -> Result<String, Box<dyn Error>> {
+        self.read_tasks_from_csv()?;
+
+        let id = self.resolve_id(&depend_args.id)?;
+        let on = depend_args.on.ok_or("The --on argument is required.")?;
+        let on = self.resolve_id(&on)?;
+
+        if depend_args.remove {
+            let task = self
+                .tasks
+                .iter_mut()
+                .find(|task| task.id == id)
+                .ok_or("Task not found.")?;
+            task.dependencies.retain(|dep| dep != &on);
+
+            self.write_tasks_to_csv()?;
+            return Ok("The dependency was successfully removed.".to_string());
+        }
+
+        if id == on {
+            return Err("A task cannot depend on itself.".into());
+        }
+
+        if self.has_path(&on, &id) {
+            return Err(format!(
+                "Adding a dependency of '{}' on '{}' would create a cycle.",
+                id, on
+            )
+            .into());
+        }
+
+        let task = self
+            .tasks
+            .iter_mut()
+            .find(|task| task.id == id)
+            .ok_or("Task not found.")?;
+        if !task.dependencies.contains(&on) {
+            task.dependencies.push(on);
+        }
+
+        self.write_tasks_to_csv()?;
+        Ok("The dependency was successfully added.".to_string())
+    }
+
+    /// Returns `true` if any of the task's dependencies are not yet done.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The task to check.
+    fn is_blocked(&self, task: &Task) -> bool {
+        task.dependencies.iter().any(|dep_id| {
+            self.tasks
+                .iter()
+                .find(|other| &other.id == dep_id)
+                .map(|other| !other.is_done)
+                .unwrap_or(false)
+        })
+    }
+
+    /// Returns `true` if `to` is reachable from `from` by following
+    /// dependency edges, used to reject cycles before adding a new one.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The task ID to start the search from.
+    /// * `to` - The task ID being searched for.
+    fn has_path(&self, from: &str, to: &str) -> bool {
+        let mut visited = HashSet::new();
+        let mut stack = vec![from.to_string()];
+
+        while let Some(current) = stack.pop() {
+            if current == to {
+                return true;
+            }
+            if !visited.insert(current.clone()) {
+                continue;
+            }
+            if let Some(task) = self.tasks.iter().find(|task| task.id == current) {
+                stack.extend(task.dependencies.iter().cloned());
+            }
+        }
+
+        false
+    }
+
     /// Writes all tasks in the collection to a CSV file.
     ///
     /// This method will overwrite the existing file if it exists, or create a new file if it does not.
@@ -220,6 +601,69 @@ impl Tasks {
             .map(char::from)
             .collect()
     }
+
+    /// Resolves a user-supplied task reference to a stored task ID.
+    ///
+    /// Accepts either a 1-based positional index (resolved against the
+    /// current ordering of `tasks`) or the full random ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id_or_index` - The task reference supplied on the command line.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, Box<dyn Error>>` - The resolved task ID, or an error if no task matches.
+    fn resolve_id(&self, id_or_index: &str) -> Result<String, Box<dyn Error>> {
+        if let Ok(index) = id_or_index.parse::<usize>() {
+            if index >= 1 {
+                if let Some(task) = self.tasks.get(index - 1) {
+                    return Ok(task.id.clone());
+                }
+            }
+        }
+
+        if self.tasks.iter().any(|task| task.id == id_or_index) {
+            return Ok(id_or_index.to_string());
+        }
+
+        Err("Task not found.".into())
+    }
+}
+
+/// Parses a comma-separated tag list into a `Vec<String>`.
+///
+/// # Arguments
+///
+/// * `tags` - The raw comma-separated tags, if any.
+fn parse_tags(tags: Option<String>) -> Vec<String> {
+    tags.map(|raw| {
+        raw.split(',')
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+/// Validates that a due date is RFC3339, e.g. `2025-01-21T00:00:00Z`.
+///
+/// # Arguments
+///
+/// * `due` - The raw due date supplied on the command line, if any.
+///
+/// # Returns
+///
+/// * `Result<Option<String>, Box<dyn Error>>` - The validated due date, or an error describing why it could not be parsed.
+fn parse_due(due: Option<String>) -> Result<Option<String>, Box<dyn Error>> {
+    match due {
+        Some(due) => {
+            DateTime::parse_from_rfc3339(&due)
+                .map_err(|e| format!("Invalid due date '{}': {} (expected RFC3339, e.g. 2025-01-21T00:00:00Z).", due, e))?;
+            Ok(Some(due))
+        }
+        None => Ok(None),
+    }
 }
 
 /// Represents a task.
@@ -229,6 +673,15 @@ struct Task {
     title: String,
     description: String,
     is_done: bool,
+    due: Option<String>,
+    #[serde(default)]
+    priority: Priority,
+    #[serde(with = "tags_serde", default)]
+    tags: Vec<String>,
+    #[serde(with = "time_entries_serde", default)]
+    time_entries: Vec<TimeEntry>,
+    #[serde(with = "dependencies_serde", default)]
+    dependencies: Vec<String>,
 }
 
 impl Task {
@@ -240,12 +693,57 @@ impl Task {
     /// * `title` - The title of the task.
     /// * `description` - The description of the task.
     /// * `is_done` - Indicates whether the task is done or not.
-    fn new(id: String, title: String, description: String, is_done: bool) -> Task {
+    /// * `due` - The due date in RFC3339 format, if any.
+    /// * `priority` - The task's priority.
+    /// * `tags` - The task's tags.
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        id: String,
+        title: String,
+        description: String,
+        is_done: bool,
+        due: Option<String>,
+        priority: Priority,
+        tags: Vec<String>,
+    ) -> Task {
         Task {
             id,
             title,
             description,
             is_done,
+            due,
+            priority,
+            tags,
+            time_entries: Vec::new(),
+            dependencies: Vec::new(),
         }
     }
+
+    /// Returns `true` if the task's due date has already passed.
+    fn is_overdue(&self) -> bool {
+        match &self.due {
+            Some(due) => match DateTime::parse_from_rfc3339(due) {
+                Ok(due) => due.with_timezone(&Utc) < Utc::now(),
+                Err(_) => false,
+            },
+            None => false,
+        }
+    }
+
+    /// Returns the total logged duration for this task, in minutes.
+    fn total_logged_minutes(&self) -> u32 {
+        self.time_entries
+            .iter()
+            .map(|entry| entry.hours * 60 + entry.minutes)
+            .sum()
+    }
+}
+
+/// Formats a duration given in minutes as `XhYm`.
+///
+/// # Arguments
+///
+/// * `total_minutes` - The duration to format, in minutes.
+fn format_duration(total_minutes: u32) -> String {
+    format!("{}h{}m", total_minutes / 60, total_minutes % 60)
 }